@@ -30,8 +30,9 @@
 //!     }
 //! }
 //!
+//! # use juniper_relay::CursorType;
 //! # let first = Some(42);
-//! # let after = Some("42".into());
+//! # let after = Some(42i32.encode_cursor());
 //! # let last = None;
 //! # let before = None;
 //! # fn run_query(sql: String) -> Vec<Foo> { vec![] };
@@ -48,19 +49,76 @@
 //!
 //! [spec]: https://relay.dev/graphql/connections.htm
 
-use juniper::{FieldResult, GraphQLObject};
+use juniper::{
+    meta::{Field, MetaType},
+    Arguments, ExecutionResult, Executor, FieldResult, GraphQLObject, GraphQLType, GraphQLValue,
+    Registry, ScalarValue,
+};
 use std::convert::TryInto;
 
 mod traits;
 
+pub use traits::{CursorDecodeError, CursorType};
+
+/// Extra fields carried by a `CF`/`EF` type parameter and flattened
+/// directly onto the generated connection's or edge's GraphQL type,
+/// alongside `edges`/`pageInfo`/`totalCount` or `node`/`cursor`. The `()`
+/// impl below is what makes `CF`/`EF` default to contributing no fields;
+/// implement this trait on your own field struct (typically by
+/// delegating to its derived [`GraphQLType`]/[`GraphQLValue`] impls, see
+/// [`RelayConnection::new_with_fields`]) to have its fields appear
+/// directly on the schema instead of needing a resolver of your own.
+///
+/// Field names must not collide with the connection's own `edges`,
+/// `pageInfo`, `totalCount` (or the edge's own `node`, `cursor`); nothing
+/// here detects that for you.
+pub trait ExtraFields<S: ScalarValue, C> {
+    /// Registers this type's fields on `registry` so they can be merged
+    /// into the parent connection's or edge's object type.
+    fn extra_fields<'r>(registry: &mut Registry<'r, S>) -> Vec<Field<'r, S>>
+    where
+        S: 'r;
+
+    /// Resolves `field_name` if it belongs to this type, or returns
+    /// `None` so the caller can fall through to its own fields.
+    fn resolve_extra_field(
+        &self,
+        field_name: &str,
+        args: &Arguments<S>,
+        executor: &Executor<C, S>,
+    ) -> Option<ExecutionResult<S>>;
+}
+
+impl<S: ScalarValue, C> ExtraFields<S, C> for () {
+    fn extra_fields<'r>(_registry: &mut Registry<'r, S>) -> Vec<Field<'r, S>>
+    where
+        S: 'r,
+    {
+        Vec::new()
+    }
+
+    fn resolve_extra_field(
+        &self,
+        _field_name: &str,
+        _args: &Arguments<S>,
+        _executor: &Executor<C, S>,
+    ) -> Option<ExecutionResult<S>> {
+        None
+    }
+}
+
 /// To return objects inside a connection, they must
 /// implement this trait.
 pub trait RelayConnectionNode {
     /// The [cursor][spec] type that is used for pagination. A cursor
-    /// should uniquely identify a given node.
+    /// should uniquely identify a given node, and is encoded into an
+    /// opaque string through [`CursorType`]. The `Ord` bound lets
+    /// [`RelayConnection`] sort fetched rows into ascending cursor order
+    /// itself, so a `_with_operation` load closure may return rows in
+    /// either direction without corrupting the page.
     ///
     /// [spec]: https://relay.dev/graphql/connections.htm#sec-Cursor
-    type Cursor: std::string::ToString + std::str::FromStr + Clone;
+    type Cursor: CursorType + Clone + Ord;
 
     /// Returns the cursor associated with this node.
     fn cursor(&self) -> Self::Cursor;
@@ -78,9 +136,87 @@ pub trait RelayConnectionNode {
 
 #[derive(Debug)]
 #[doc(hidden)]
-pub struct RelayConnectionEdge<N> {
+pub struct RelayConnectionEdge<N, EF = ()> {
     node: N,
     cursor: String,
+    fields: EF,
+}
+
+impl<S, N, EF> GraphQLType<S> for RelayConnectionEdge<N, EF>
+where
+    S: ScalarValue,
+    N: RelayConnectionNode + GraphQLType<S, TypeInfo = ()>,
+    EF: ExtraFields<S, N::Context>,
+{
+    fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+        Some(N::edge_type_name())
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let mut fields = vec![
+            registry.field::<N>("node", info),
+            registry.field::<String>("cursor", info),
+        ];
+        fields.extend(EF::extra_fields(registry));
+        registry.build_object_type::<Self>(info, &fields).into_meta()
+    }
+}
+
+impl<S, N, EF> GraphQLValue<S> for RelayConnectionEdge<N, EF>
+where
+    S: ScalarValue,
+    N: RelayConnectionNode + GraphQLType<S, TypeInfo = ()>,
+    EF: ExtraFields<S, N::Context>,
+{
+    type Context = N::Context;
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field_name: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match field_name {
+            "node" => executor.resolve_with_ctx(info, &self.node),
+            "cursor" => executor.resolve_with_ctx(info, &self.cursor),
+            _ => self
+                .fields
+                .resolve_extra_field(field_name, args, executor)
+                .unwrap_or_else(|| {
+                    panic!("Field {field_name} not found on type {}", N::edge_type_name())
+                }),
+        }
+    }
+}
+
+impl<N, EF> RelayConnectionEdge<N, EF> {
+    /// Returns the node wrapped by this edge.
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+
+    /// Returns this edge's opaque, already-encoded cursor.
+    pub fn cursor(&self) -> &str {
+        &self.cursor
+    }
+
+    /// Returns the edge-level `EF` fields attached to this edge, e.g. via
+    /// [`new_with_fields`](RelayConnection::new_with_fields). When `EF`
+    /// implements [`ExtraFields`], these fields are also flattened
+    /// directly onto the edge's GraphQL type; this getter is for use from
+    /// your own Rust code.
+    pub fn fields(&self) -> &EF {
+        &self.fields
+    }
 }
 
 #[derive(Debug, GraphQLObject)]
@@ -96,11 +232,134 @@ pub struct RelayConnectionPageInfo {
 /// Implements the relay connection [specification][spec], and allows to
 /// easily paginate over any given list of GraphQL objects.
 ///
+/// The `CF` and `EF` type parameters are optional connection-level and
+/// per-edge field bags, for data that doesn't belong on the node itself,
+/// e.g. a connection-level `appliedFilters` object or an edge-level
+/// `score`. Both default to `()`, which contributes no extra fields.
+/// Implement [`ExtraFields`] for a `CF`/`EF` type to have its fields
+/// flattened directly onto the generated connection/edge type.
+///
 /// [spec]: https://relay.dev/graphql/connections.htm
 #[derive(Debug)]
-pub struct RelayConnection<N> {
-    edges: Vec<RelayConnectionEdge<N>>,
+pub struct RelayConnection<N, CF = (), EF = ()> {
+    edges: Vec<RelayConnectionEdge<N, EF>>,
     page_info: RelayConnectionPageInfo,
+    total_count: Option<i64>,
+    fields: CF,
+}
+
+impl<S, N, CF, EF> GraphQLType<S> for RelayConnection<N, CF, EF>
+where
+    S: ScalarValue,
+    N: RelayConnectionNode + GraphQLType<S, TypeInfo = ()>,
+    CF: ExtraFields<S, N::Context>,
+    EF: ExtraFields<S, N::Context>,
+{
+    fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+        Some(N::connection_type_name())
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        let mut fields = vec![
+            registry.field::<Vec<RelayConnectionEdge<N, EF>>>("edges", info),
+            registry.field::<RelayConnectionPageInfo>("pageInfo", info),
+            registry.field::<Option<i64>>("totalCount", info),
+        ];
+        fields.extend(CF::extra_fields(registry));
+        registry.build_object_type::<Self>(info, &fields).into_meta()
+    }
+}
+
+impl<S, N, CF, EF> GraphQLValue<S> for RelayConnection<N, CF, EF>
+where
+    S: ScalarValue,
+    N: RelayConnectionNode + GraphQLType<S, TypeInfo = ()>,
+    CF: ExtraFields<S, N::Context>,
+    EF: ExtraFields<S, N::Context>,
+{
+    type Context = N::Context;
+    type TypeInfo = ();
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        <Self as GraphQLType<S>>::name(info)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field_name: &str,
+        args: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> ExecutionResult<S> {
+        match field_name {
+            "edges" => executor.resolve_with_ctx(info, &self.edges),
+            "pageInfo" => executor.resolve_with_ctx(info, &self.page_info),
+            "totalCount" => executor.resolve_with_ctx(info, &self.total_count),
+            _ => self
+                .fields
+                .resolve_extra_field(field_name, args, executor)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Field {field_name} not found on type {}",
+                        N::connection_type_name()
+                    )
+                }),
+        }
+    }
+}
+
+impl<N, CF, EF> RelayConnection<N, CF, EF> {
+    /// Returns the connection-level `CF` fields attached to this
+    /// connection, e.g. via
+    /// [`new_with_fields`](RelayConnection::new_with_fields). When `CF`
+    /// implements [`ExtraFields`], these fields are also flattened
+    /// directly onto the connection's GraphQL type; this getter is for
+    /// use from your own Rust code.
+    pub fn fields(&self) -> &CF {
+        &self.fields
+    }
+}
+
+/// The precise pagination request a [`RelayConnection`] has been asked
+/// for, handed to the `_with_operation` constructors' load closure so a
+/// backend can push the limit down to SQL in both directions, not just
+/// `first`. Borrowed from async-graphql's `QueryOperation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryOperation<C> {
+    /// Neither `first` nor `last` was given; load every row between
+    /// `after` and `before`.
+    All {
+        /// Lower cursor bound, exclusive.
+        after: Option<C>,
+        /// Upper cursor bound, exclusive.
+        before: Option<C>,
+    },
+    /// Forward pagination. Fetch at most `limit` rows with cursor after
+    /// `after` and before `before`, in ascending cursor order.
+    First {
+        /// `first + 1`, so the extra row reveals `hasNextPage`.
+        limit: i64,
+        /// Lower cursor bound, exclusive.
+        after: Option<C>,
+        /// Upper cursor bound, exclusive.
+        before: Option<C>,
+    },
+    /// Backward pagination. Fetch at most `limit` *trailing* rows with
+    /// cursor after `after` and before `before`, e.g.
+    /// `ORDER BY cursor DESC LIMIT limit` between the bounds. The closure
+    /// may return the fetched nodes in either cursor order; they are
+    /// sorted into ascending order internally before the page is sliced.
+    Last {
+        /// `last + 1`, so the extra row reveals `hasPreviousPage`.
+        limit: i64,
+        /// Lower cursor bound, exclusive.
+        after: Option<C>,
+        /// Upper cursor bound, exclusive.
+        before: Option<C>,
+    },
 }
 
 fn leq_zero(val: i64) -> Result<i64, &'static str> {
@@ -111,30 +370,60 @@ fn leq_zero(val: i64) -> Result<i64, &'static str> {
     }
 }
 
-impl<N> RelayConnection<N>
+/// Converts a [`QueryOperation`] back into the `(after, before, limit)`
+/// triple the legacy three-argument load closures expect.
+fn operation_bounds<C>(op: QueryOperation<C>) -> (Option<C>, Option<C>, Option<i64>) {
+    match op {
+        QueryOperation::All { after, before } | QueryOperation::Last { after, before, .. } => {
+            (after, before, None)
+        }
+        QueryOperation::First { limit, after, before } => (after, before, Some(limit)),
+    }
+}
+
+impl<N, CF, EF> RelayConnection<N, CF, EF>
 where
     N: RelayConnectionNode,
-    <N::Cursor as std::str::FromStr>::Err: std::fmt::Display,
 {
-    fn closure_args(
+    fn query_operation(
         first: Option<i64>,
+        last: Option<i64>,
         after: Option<String>,
         before: Option<String>,
-    ) -> FieldResult<(Option<N::Cursor>, Option<N::Cursor>, Option<i64>)> {
-        let after: Option<N::Cursor> = after.map(|s| s.parse()).transpose()?;
-        let before: Option<N::Cursor> = before.map(|s| s.parse()).transpose()?;
+    ) -> FieldResult<QueryOperation<N::Cursor>> {
+        let after: Option<N::Cursor> = after.map(|s| N::Cursor::decode_cursor(&s)).transpose()?;
+        let before: Option<N::Cursor> = before.map(|s| N::Cursor::decode_cursor(&s)).transpose()?;
 
-        // to ensure `hasNextPage` can be set correctly
-        let limit = first.map(|l| l + 1);
-
-        Ok((after, before, limit))
+        Ok(if let Some(first) = first {
+            QueryOperation::First {
+                limit: first + 1,
+                after,
+                before,
+            }
+        } else if let Some(last) = last {
+            QueryOperation::Last {
+                limit: last + 1,
+                after,
+                before,
+            }
+        } else {
+            QueryOperation::All { after, before }
+        })
     }
 
     fn build_connection(
         first: Option<i64>,
         last: Option<i64>,
-        edges: Vec<N>,
-    ) -> FieldResult<RelayConnection<N>> {
+        mut edges: Vec<(N, EF)>,
+        total_count: Option<i64>,
+        fields: CF,
+    ) -> FieldResult<RelayConnection<N, CF, EF>> {
+        // Normalize to ascending cursor order regardless of what order the
+        // load closure fetched rows in, so backward (`last`) pagination is
+        // correct even when the closure's `ORDER BY ... DESC` result is
+        // handed back verbatim.
+        edges.sort_by(|(a, _), (b, _)| a.cursor().cmp(&b.cursor()));
+
         let edges_len: i64 = edges.len().try_into()?;
 
         let has_previous_page = if let Some(last) = last {
@@ -154,13 +443,14 @@ where
         let len_after_take = i64::min(edges_len, first);
         let skip = i64::max(0, len_after_take - last);
 
-        let edges: Vec<RelayConnectionEdge<N>> = edges
+        let edges: Vec<RelayConnectionEdge<N, EF>> = edges
             .into_iter()
             .take(first.try_into()?)
             .skip(skip.try_into()?)
-            .map(|node| RelayConnectionEdge {
-                cursor: node.cursor().to_string(),
+            .map(|(node, fields)| RelayConnectionEdge {
+                cursor: node.cursor().encode_cursor(),
                 node,
+                fields,
             })
             .collect();
 
@@ -172,6 +462,8 @@ where
                 end_cursor: edges.last().map(|edge| edge.cursor.clone()),
             },
             edges,
+            total_count,
+            fields,
         })
     }
 
@@ -193,15 +485,17 @@ where
         last: Option<i32>,
         before: Option<String>,
         load: L,
-    ) -> FieldResult<RelayConnection<N>>
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
     where
         L: FnOnce(Option<N::Cursor>, Option<N::Cursor>, Option<i64>) -> FieldResult<Vec<N>>,
+        CF: Default,
+        EF: Default,
     {
-        let first: Option<i64> = first.map(Into::into).map(leq_zero).transpose()?;
-        let last: Option<i64> = last.map(Into::into).map(leq_zero).transpose()?;
-        let (after, before, limit) = Self::closure_args(first, after, before)?;
-        let edges = load(after, before, limit)?;
-        Self::build_connection(first, last, edges)
+        Self::new_with_operation(first, after, last, before, CF::default(), |op| {
+            let (after, before, limit) = operation_bounds(op);
+            let edges = load(after, before, limit)?;
+            Ok((edges.into_iter().map(|node| (node, EF::default())).collect(), None))
+        })
     }
 
     /// The same as [`new`](Self::new), but with an `async` closure.
@@ -211,20 +505,213 @@ where
         last: Option<i32>,
         before: Option<String>,
         load: L,
-    ) -> FieldResult<RelayConnection<N>>
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
     where
         L: FnOnce(Option<N::Cursor>, Option<N::Cursor>, Option<i64>) -> F,
         F: std::future::Future<Output = FieldResult<Vec<N>>>,
+        CF: Default,
+        EF: Default,
+    {
+        Self::new_async_with_operation(first, after, last, before, CF::default(), |op| async move {
+            let (after, before, limit) = operation_bounds(op);
+            let edges = load(after, before, limit).await?;
+            Ok((edges.into_iter().map(|node| (node, EF::default())).collect(), None))
+        })
+        .await
+    }
+
+    /// The same as [`new`](Self::new), but the closure is given a single
+    /// [`QueryOperation`] describing the exact pagination request instead
+    /// of a raw `(after, before, limit)` triple, and returns the per-edge
+    /// `EF` fields alongside each node plus an optional total count. The
+    /// connection-level `fields` are supplied directly as an argument
+    /// rather than returned by the closure. Unlike `new`, this lets a SQL
+    /// backend push the limit down on `last`-based (backward) pagination
+    /// too, rather than loading the whole range and slicing it in memory.
+    /// This and [`new_async_with_operation`](Self::new_async_with_operation)
+    /// are the primitives the other `new*` constructors are built on top of.
+    pub fn new_with_operation<L>(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        fields: CF,
+        load: L,
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
+    where
+        L: FnOnce(QueryOperation<N::Cursor>) -> FieldResult<(Vec<(N, EF)>, Option<i64>)>,
+    {
+        let first: Option<i64> = first.map(Into::into).map(leq_zero).transpose()?;
+        let last: Option<i64> = last.map(Into::into).map(leq_zero).transpose()?;
+        let op = Self::query_operation(first, last, after, before)?;
+        let (edges, total_count) = load(op)?;
+        Self::build_connection(first, last, edges, total_count, fields)
+    }
+
+    /// The same as [`new_with_operation`](Self::new_with_operation), but
+    /// with an `async` closure.
+    pub async fn new_async_with_operation<L, F>(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        fields: CF,
+        load: L,
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
+    where
+        L: FnOnce(QueryOperation<N::Cursor>) -> F,
+        F: std::future::Future<Output = FieldResult<(Vec<(N, EF)>, Option<i64>)>>,
     {
         let first: Option<i64> = first.map(Into::into).map(leq_zero).transpose()?;
         let last: Option<i64> = last.map(Into::into).map(leq_zero).transpose()?;
-        let (after, before, limit) = Self::closure_args(first, after, before)?;
-        let edges = load(after, before, limit).await?;
-        Self::build_connection(first, last, edges)
+        let op = Self::query_operation(first, last, after, before)?;
+        let (edges, total_count) = load(op).await?;
+        Self::build_connection(first, last, edges, total_count, fields)
+    }
+
+    /// The same as [`new`](Self::new), but the closure additionally
+    /// returns the total number of rows matching the query, ignoring
+    /// `first`/`last`/`after`/`before`. This is exposed as the nullable
+    /// `totalCount` connection field, letting clients render e.g.
+    /// "showing 20 of 4,312" without a separate query.
+    pub fn new_with_total<L>(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        load: L,
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
+    where
+        L: FnOnce(Option<N::Cursor>, Option<N::Cursor>, Option<i64>) -> FieldResult<(Vec<N>, i64)>,
+        CF: Default,
+        EF: Default,
+    {
+        Self::new_with_operation(first, after, last, before, CF::default(), |op| {
+            let (after, before, limit) = operation_bounds(op);
+            let (edges, total_count) = load(after, before, limit)?;
+            Ok((
+                edges.into_iter().map(|node| (node, EF::default())).collect(),
+                Some(total_count),
+            ))
+        })
+    }
+
+    /// The same as [`new_with_total`](Self::new_with_total), but with an
+    /// `async` closure.
+    pub async fn new_async_with_total<L, F>(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        load: L,
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
+    where
+        L: FnOnce(Option<N::Cursor>, Option<N::Cursor>, Option<i64>) -> F,
+        F: std::future::Future<Output = FieldResult<(Vec<N>, i64)>>,
+        CF: Default,
+        EF: Default,
+    {
+        Self::new_async_with_operation(first, after, last, before, CF::default(), |op| async move {
+            let (after, before, limit) = operation_bounds(op);
+            let (edges, total_count) = load(after, before, limit).await?;
+            Ok((
+                edges.into_iter().map(|node| (node, EF::default())).collect(),
+                Some(total_count),
+            ))
+        })
+        .await
+    }
+
+    /// The same as [`new`](Self::new), but also attaches connection-level
+    /// `fields` and lets the closure return per-edge `EF` fields
+    /// alongside each node, e.g. an edge-level `score` or `distance`.
+    pub fn new_with_fields<L>(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        fields: CF,
+        load: L,
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
+    where
+        L: FnOnce(Option<N::Cursor>, Option<N::Cursor>, Option<i64>) -> FieldResult<Vec<(N, EF)>>,
+    {
+        Self::new_with_operation(first, after, last, before, fields, |op| {
+            let (after, before, limit) = operation_bounds(op);
+            Ok((load(after, before, limit)?, None))
+        })
+    }
+
+    /// The same as [`new_with_fields`](Self::new_with_fields), but with an
+    /// `async` closure.
+    pub async fn new_async_with_fields<L, F>(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        fields: CF,
+        load: L,
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
+    where
+        L: FnOnce(Option<N::Cursor>, Option<N::Cursor>, Option<i64>) -> F,
+        F: std::future::Future<Output = FieldResult<Vec<(N, EF)>>>,
+    {
+        Self::new_async_with_operation(first, after, last, before, fields, |op| async move {
+            let (after, before, limit) = operation_bounds(op);
+            Ok((load(after, before, limit).await?, None))
+        })
+        .await
+    }
+
+    /// The same as [`new_with_operation`](Self::new_with_operation), but
+    /// for backends that expose their rows as a [`futures::Stream`]
+    /// rather than a `Vec`, e.g. a database row stream or a chunked HTTP
+    /// API. At most `limit` items are pulled from the stream before
+    /// polling stops, so a backend streaming from a cancellable query
+    /// doesn't have to materialize the full result set just to discover
+    /// there was one extra row.
+    pub async fn from_stream<L, S>(
+        first: Option<i32>,
+        after: Option<String>,
+        last: Option<i32>,
+        before: Option<String>,
+        fields: CF,
+        total_count: Option<i64>,
+        stream_fn: L,
+    ) -> FieldResult<RelayConnection<N, CF, EF>>
+    where
+        L: FnOnce(QueryOperation<N::Cursor>) -> S,
+        S: futures::Stream<Item = FieldResult<(N, EF)>>,
+    {
+        let first: Option<i64> = first.map(Into::into).map(leq_zero).transpose()?;
+        let last: Option<i64> = last.map(Into::into).map(leq_zero).transpose()?;
+        let op = Self::query_operation(first, last, after, before)?;
+        let limit = match &op {
+            QueryOperation::First { limit, .. } | QueryOperation::Last { limit, .. } => {
+                Some(*limit)
+            }
+            QueryOperation::All { .. } => None,
+        };
+
+        let stream = stream_fn(op);
+        futures::pin_mut!(stream);
+
+        let mut edges = Vec::new();
+        while let Some(edge) = futures::StreamExt::next(&mut stream).await {
+            edges.push(edge?);
+            if limit.is_some_and(|limit| edges.len() as i64 >= limit) {
+                break;
+            }
+        }
+
+        Self::build_connection(first, last, edges, total_count, fields)
     }
 
     /// Returns a relay connection with no elements.
-    pub fn empty() -> Self {
+    pub fn empty() -> Self
+    where
+        CF: Default,
+    {
         Self {
             edges: vec![],
             page_info: RelayConnectionPageInfo {
@@ -233,8 +720,18 @@ where
                 start_cursor: None,
                 end_cursor: None,
             },
+            total_count: None,
+            fields: CF::default(),
         }
     }
+
+    /// Returns the total number of rows matching the query, if one was
+    /// supplied when building this connection (by the `_with_total`
+    /// constructors, or through `new_with_operation`,
+    /// `new_async_with_operation` or `from_stream`), or `None` otherwise.
+    pub fn total_count(&self) -> Option<i64> {
+        self.total_count
+    }
 }
 
 #[cfg(test)]
@@ -263,17 +760,144 @@ mod test {
     }
 
     #[test]
-    fn closure_args_smoke_test() {
+    fn query_operation_decodes_pagination_args() {
+        assert_eq!(
+            RelayConnection::<FakeNode>::query_operation(None, None, None, None).unwrap(),
+            QueryOperation::All {
+                after: None,
+                before: None
+            }
+        );
         assert_eq!(
-            RelayConnection::<FakeNode>::closure_args(Some(42), Some("8".into()), None),
-            Ok((Some(8), None, Some(43)))
+            RelayConnection::<FakeNode>::query_operation(
+                Some(42),
+                None,
+                Some(8i32.encode_cursor()),
+                None
+            )
+            .unwrap(),
+            QueryOperation::First {
+                limit: 43,
+                after: Some(8),
+                before: None
+            }
         );
         assert_eq!(
-            RelayConnection::<FakeNode>::closure_args(None, None, Some("95".into())),
-            Ok((None, Some(95), None))
+            RelayConnection::<FakeNode>::query_operation(
+                None,
+                Some(12),
+                None,
+                Some(95i32.encode_cursor())
+            )
+            .unwrap(),
+            QueryOperation::Last {
+                limit: 13,
+                after: None,
+                before: Some(95)
+            }
         );
         assert!(
-            RelayConnection::<FakeNode>::closure_args(None, Some("foo".to_string()), None).is_err()
+            RelayConnection::<FakeNode>::query_operation(None, None, Some("foo".to_string()), None)
+                .is_err()
         );
     }
+
+    #[test]
+    fn new_with_operation_sorts_last_pages_into_ascending_order() {
+        // A `last`-only request asks the closure to fetch trailing rows,
+        // e.g. via `ORDER BY cursor DESC`. Per `QueryOperation::Last`'s
+        // contract, handing the DESC-ordered rows straight back must still
+        // produce a correctly ordered page.
+        let connection =
+            RelayConnection::<FakeNode>::new_with_operation(None, None, Some(2), None, (), |op| {
+                assert_eq!(
+                    op,
+                    QueryOperation::Last {
+                        limit: 3,
+                        after: None,
+                        before: None
+                    }
+                );
+                let edges = vec![
+                    (FakeNode { id: 5 }, ()),
+                    (FakeNode { id: 4 }, ()),
+                    (FakeNode { id: 3 }, ()),
+                ];
+                Ok((edges, None))
+            })
+            .unwrap();
+
+        let ids: Vec<i32> = connection.edges.iter().map(|edge| edge.node().id).collect();
+        assert_eq!(ids, vec![4, 5]);
+        assert!(connection.page_info.has_previous_page);
+        assert!(!connection.page_info.has_next_page);
+    }
+
+    #[test]
+    fn from_stream_stops_polling_after_limit() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+
+        let polled = Rc::new(Cell::new(0));
+        let polled_in_stream = Rc::clone(&polled);
+
+        let nodes: Vec<FieldResult<(FakeNode, ())>> =
+            (1..=10).map(|id| Ok((FakeNode { id }, ()))).collect();
+
+        let connection = futures::executor::block_on(RelayConnection::<FakeNode>::from_stream(
+            Some(3),
+            None,
+            None,
+            None,
+            (),
+            None,
+            |_op| {
+                futures::StreamExt::inspect(futures::stream::iter(nodes), move |_| {
+                    polled_in_stream.set(polled_in_stream.get() + 1);
+                })
+            },
+        ))
+        .unwrap();
+
+        // `first: Some(3)` asks for a limit of 4 (the extra row reveals
+        // `hasNextPage`); the stream must stop being polled right there,
+        // not after draining all 10 available items.
+        assert_eq!(polled.get(), 4);
+        assert_eq!(connection.edges.len(), 3);
+        assert!(connection.page_info.has_next_page);
+    }
+
+    #[test]
+    fn cf_ef_getters_round_trip() {
+        let connection = RelayConnection::<FakeNode, &'static str, i32>::new_with_fields(
+            None,
+            None,
+            None,
+            None,
+            "applied-filters",
+            |_, _, _| Ok(vec![(FakeNode { id: 1 }, 99)]),
+        )
+        .unwrap();
+
+        assert_eq!(connection.fields(), &"applied-filters");
+        assert_eq!(connection.edges[0].node().id, 1);
+        assert_eq!(connection.edges[0].fields(), &99);
+        assert_eq!(
+            connection.edges[0].cursor(),
+            connection.page_info.start_cursor.as_deref().unwrap()
+        );
+    }
+
+    #[test]
+    fn total_count_getter_round_trips() {
+        let connection =
+            RelayConnection::<FakeNode>::new_with_total(None, None, None, None, |_, _, _| {
+                Ok((vec![FakeNode { id: 1 }], 4312))
+            })
+            .unwrap();
+        assert_eq!(connection.total_count(), Some(4312));
+
+        let connection = RelayConnection::<FakeNode>::empty();
+        assert_eq!(connection.total_count(), None);
+    }
 }
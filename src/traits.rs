@@ -0,0 +1,75 @@
+//! Internal helpers backing [`crate::RelayConnectionNode::Cursor`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+/// Encodes and decodes the opaque string cursors sent to, and received
+/// from, clients.
+///
+/// A cursor must uniquely identify a node, but the Relay [spec][spec]
+/// also expects it to be *opaque*: clients should treat it as an
+/// unstructured token, not a value they can inspect or construct
+/// themselves. A blanket implementation is provided for every type that
+/// already implements [`ToString`] and [`FromStr`], which base64-encodes
+/// the `ToString` output and base64-decodes before parsing it back.
+///
+/// To customize the encoding, e.g. to prefix a cursor with its
+/// connection's type name so it can't be replayed against a different
+/// connection, implement `CursorType` directly for your cursor type
+/// instead of relying on the blanket implementation (this requires the
+/// type not implement `ToString`/`FromStr` itself, since the blanket
+/// implementation would otherwise conflict).
+///
+/// [spec]: https://relay.dev/graphql/connections.htm#sec-Cursor
+pub trait CursorType: Sized {
+    /// The error returned when a cursor string could not be decoded.
+    type Error: fmt::Display;
+
+    /// Encodes `self` into the opaque cursor string handed to clients.
+    fn encode_cursor(&self) -> String;
+
+    /// Decodes a cursor string previously produced by
+    /// [`encode_cursor`](Self::encode_cursor).
+    fn decode_cursor(cursor: &str) -> Result<Self, Self::Error>;
+}
+
+/// The error returned by the blanket [`CursorType`] implementation.
+#[derive(Debug)]
+pub enum CursorDecodeError<E> {
+    /// The cursor was not valid base64, or did not decode to valid UTF-8.
+    InvalidEncoding,
+    /// The cursor was valid base64, but the decoded value could not be
+    /// parsed.
+    InvalidValue(E),
+}
+
+impl<E: fmt::Display> fmt::Display for CursorDecodeError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidEncoding => write!(f, "cursor is not validly encoded"),
+            Self::InvalidValue(err) => write!(f, "cursor could not be parsed: {err}"),
+        }
+    }
+}
+
+impl<T> CursorType for T
+where
+    T: ToString + FromStr,
+    T::Err: fmt::Display,
+{
+    type Error = CursorDecodeError<T::Err>;
+
+    fn encode_cursor(&self) -> String {
+        STANDARD.encode(self.to_string())
+    }
+
+    fn decode_cursor(cursor: &str) -> Result<Self, Self::Error> {
+        let decoded = STANDARD
+            .decode(cursor)
+            .map_err(|_| CursorDecodeError::InvalidEncoding)?;
+        let decoded = String::from_utf8(decoded).map_err(|_| CursorDecodeError::InvalidEncoding)?;
+        decoded.parse().map_err(CursorDecodeError::InvalidValue)
+    }
+}